@@ -0,0 +1,128 @@
+use axum::{
+    extract::{FromRequestParts, State},
+    http::{request::Parts, StatusCode},
+    middleware,
+    response::{IntoResponse, Json as JsonResponse},
+    routing::post,
+    Json, Router,
+};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::{error::AppError, middleware::ratelimit::rate_limit, AppState};
+
+/// Mounted behind the same rate limiter as the write endpoints it gates access to, since
+/// it's the endpoint an attacker would brute-force to guess `ingestion_api_key`.
+pub fn create_router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/token", post(issue_token))
+        .route_layer(middleware::from_fn(rate_limit))
+}
+
+/// Constant-time equality check so comparing a guessed API key against the real one
+/// doesn't leak the length of the matching prefix through response timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter()
+        .zip(b)
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub role: String,
+    pub exp: i64,
+}
+
+/// Extractor requiring a valid `Authorization: Bearer <jwt>` header.
+pub struct AuthUser {
+    pub claims: Claims,
+}
+
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get(axum::http::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(AppError::Unauthorized)?;
+
+        let token = header.strip_prefix("Bearer ").ok_or(AppError::Unauthorized)?;
+
+        let claims = decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(state.config.jwt_secret.as_bytes()),
+            &Validation::new(jsonwebtoken::Algorithm::HS256),
+        )
+        .map_err(|_| AppError::Unauthorized)?
+        .claims;
+
+        Ok(AuthUser { claims })
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TokenRequest {
+    api_key: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenResponse {
+    token: String,
+}
+
+/// Issues a signed JWT for the configured ingestion API key so the scraper feeding
+/// `batch_upload` can authenticate against the write endpoints.
+#[utoipa::path(
+    post,
+    path = "/api/v1/token",
+    request_body = TokenRequest,
+    responses(
+        (status = 200, description = "A signed JWT for the ingestion role", body = TokenResponse),
+        (status = 401, description = "Invalid API key"),
+    ),
+    tag = "auth"
+)]
+#[instrument(skip(state, payload))]
+pub async fn issue_token(
+    State(state): State<AppState>,
+    Json(payload): Json<TokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !constant_time_eq(
+        payload.api_key.as_bytes(),
+        state.config.ingestion_api_key.as_bytes(),
+    ) {
+        return Err(AppError::Unauthorized);
+    }
+
+    let exp = (chrono::Utc::now() + chrono::Duration::hours(12)).timestamp();
+    let claims = Claims {
+        sub: "ingestion".to_string(),
+        role: "ingestion".to_string(),
+        exp,
+    };
+
+    let token = encode(
+        &Header::new(jsonwebtoken::Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(state.config.jwt_secret.as_bytes()),
+    )
+    .map_err(|e| AppError::Validation {
+        message: format!("Failed to sign token: {e}"),
+    })?;
+
+    Ok((StatusCode::OK, JsonResponse(json!(TokenResponse { token }))))
+}
@@ -8,6 +8,23 @@ pub struct Config {
     pub port: u16,
     #[serde(default = "default_log_level")]
     pub log_level: String,
+    #[serde(default = "default_rate_limit_requests_per_window")]
+    pub rate_limit_requests_per_window: u32,
+    #[serde(default = "default_rate_limit_window_secs")]
+    pub rate_limit_window_secs: u64,
+    /// HS256 secret used to sign and verify JWTs for write-endpoint auth.
+    pub jwt_secret: String,
+    /// API key the ingestion scraper exchanges for a JWT via `POST /api/v1/token`.
+    pub ingestion_api_key: String,
+    #[serde(default = "default_max_request_body_bytes")]
+    pub max_request_body_bytes: usize,
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+    /// Comma-separated IPs of reverse proxies allowed to set `X-Forwarded-For`. The
+    /// immediate TCP peer must be one of these before the header is trusted; otherwise the
+    /// rate limiter keys on the connection's socket address.
+    #[serde(default = "default_trusted_proxies")]
+    pub trusted_proxies: String,
 }
 
 fn default_database_url() -> String {
@@ -22,6 +39,26 @@ fn default_log_level() -> String {
     "info".to_string()
 }
 
+fn default_rate_limit_requests_per_window() -> u32 {
+    100
+}
+
+fn default_rate_limit_window_secs() -> u64 {
+    60
+}
+
+fn default_max_request_body_bytes() -> usize {
+    2 * 1024 * 1024
+}
+
+fn default_max_batch_size() -> usize {
+    500
+}
+
+fn default_trusted_proxies() -> String {
+    String::new()
+}
+
 impl Config {
     pub fn load() -> anyhow::Result<Self> {
         dotenvy::dotenv().ok();
@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct HeroStats {
     pub id: i32,
     pub hero_id: String,
@@ -19,7 +20,7 @@ pub struct HeroStats {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub struct CreateHeroStats {
     pub hero_id: String,
@@ -49,7 +50,7 @@ pub struct UpdateHeroStats {
     pub timestamp: Option<DateTime<Utc>>,
 }
 
-#[derive(sqlx::Type, Clone, Serialize, Deserialize, Debug)]
+#[derive(sqlx::Type, Clone, Serialize, Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "lowercase")]
 #[sqlx(type_name = "hero_class_enum", rename_all = "lowercase")]
 pub enum HeroClass {
@@ -58,7 +59,7 @@ pub enum HeroClass {
     Tank,
 }
 
-#[derive(sqlx::Type, Clone, Serialize, Deserialize, Debug)]
+#[derive(sqlx::Type, Clone, Serialize, Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "lowercase")]
 #[sqlx(type_name = "map_type_enum", rename_all = "lowercase")]
 pub enum MapType {
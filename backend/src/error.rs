@@ -1,5 +1,5 @@
 use axum::{
-    http::StatusCode,
+    http::{HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -10,20 +10,39 @@ use thiserror::Error;
 pub enum AppError {
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
-    
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
-    
+
     #[error("Validation error: {message}")]
     Validation { message: String },
-    
+
     #[error("Not found: {resource}")]
     NotFound { resource: String },
-    
+
+    #[error("Rate limit exceeded")]
+    RateLimited { retry_after_secs: u64 },
+
+    #[error("Unauthorized")]
+    Unauthorized,
+
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        if let AppError::RateLimited { retry_after_secs } = self {
+            let body = Json(json!({
+                "error": "Too many requests",
+                "status": StatusCode::TOO_MANY_REQUESTS.as_u16()
+            }));
+            let mut response = (StatusCode::TOO_MANY_REQUESTS, body).into_response();
+            response.headers_mut().insert(
+                "retry-after",
+                HeaderValue::from_str(&retry_after_secs.to_string()).unwrap(),
+            );
+            return response;
+        }
+
         let (status, error_message) = match self {
             AppError::Database(err) => {
                 tracing::error!("Database error: {}", err);
@@ -34,6 +53,10 @@ impl IntoResponse for AppError {
             AppError::NotFound { ref resource } => {
                 (StatusCode::NOT_FOUND, format!("{} not found", resource))
             },
+            AppError::Unauthorized => {
+                (StatusCode::UNAUTHORIZED, "Missing or invalid bearer token".to_string())
+            }
+            AppError::RateLimited { .. } => unreachable!("handled above"),
         };
 
         let body = Json(json!({
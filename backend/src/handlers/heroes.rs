@@ -1,33 +1,54 @@
 use axum::{
     extract::{Json, Path, Query, State},
     http::StatusCode,
+    middleware,
+    response::sse::{Event, KeepAlive, Sse},
     response::IntoResponse,
     response::Json as JsonResponse,
     routing::{get, post},
     Router,
 };
 use serde_json::{json, Value};
+use std::convert::Infallible;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tower_http::compression::CompressionLayer;
 use tracing::{info, instrument};
 
 use crate::{
+    auth::AuthUser,
     database::models::{CreateHeroStats, HeroStats},
     error::AppError,
+    middleware::ratelimit::rate_limit,
     AppState,
 };
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::{Postgres, QueryBuilder};
 use std::fmt;
+use utoipa::ToSchema;
 
+/// Buffered JSON endpoints get gzip/br compression; `/api/v1/heroes/stream` is mounted
+/// outside this sub-router because compressing an SSE stream delays event delivery until
+/// the encoder has enough bytes to flush, defeating the point of a live stream.
 pub fn create_router() -> Router<AppState> {
-    Router::new()
-        .route("/api/v1/test", get(|| async { "api routes working" }))
+    let limited = Router::new()
         .route("/api/v1/heroes", get(get_heroes).post(create_hero))
         .route("/api/v1/hero/:id", get(get_hero).delete(delete_hero))
         .route("/api/v1/heroes/batch", post(batch_upload))
+        .route("/api/v1/heroes/aggregate", get(aggregate_heroes))
+        .route_layer(middleware::from_fn(rate_limit));
+
+    let json_routes = Router::new()
+        .route("/api/v1/test", get(|| async { "api routes working" }))
+        .merge(limited)
+        .layer(CompressionLayer::new());
+
+    Router::new()
+        .route("/api/v1/heroes/stream", get(stream_heroes))
+        .merge(json_routes)
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, ToSchema)]
 pub struct HeroQueryParams {
     hero_id: Option<String>,
     region: Option<String>,
@@ -39,10 +60,28 @@ pub struct HeroQueryParams {
     end_time: Option<DateTime<Utc>>,
     order_by: Option<OrderBy>,
     order: Option<Order>,
-    _limit: Option<usize>,
+    limit: Option<i64>,
+    offset: Option<i64>,
 }
 
-#[derive(Deserialize, Debug)]
+const DEFAULT_PAGE_LIMIT: i64 = 100;
+const MAX_PAGE_LIMIT: i64 = 500;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Pagination {
+    total: i64,
+    limit: i64,
+    offset: i64,
+    has_more: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HeroesEnvelope {
+    data: Vec<HeroStats>,
+    pagination: Pagination,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderBy {
     PickRate,
@@ -50,7 +89,7 @@ pub enum OrderBy {
     InsertedAt,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Order {
     Asc,
@@ -76,73 +115,247 @@ impl fmt::Display for Order {
     }
 }
 
+#[derive(Deserialize, Debug)]
+pub struct HeroStreamParams {
+    hero_id: Option<String>,
+    region: Option<String>,
+    tier: Option<String>,
+}
+
+fn matches_stream_filters(hero: &HeroStats, params: &HeroStreamParams) -> bool {
+    if let Some(hero_id) = &params.hero_id {
+        if &hero.hero_id != hero_id {
+            return false;
+        }
+    }
+    if let Some(region) = &params.region {
+        if &hero.region != region {
+            return false;
+        }
+    }
+    if let Some(tier) = &params.tier {
+        if &hero.tier != tier {
+            return false;
+        }
+    }
+    true
+}
+
+/// Streams newly inserted `HeroStats` rows as Server-Sent Events, optionally filtered by
+/// `hero_id`/`region`/`tier`, so dashboards can watch updates without polling `get_heroes`.
 #[instrument(skip(state))]
-pub async fn get_heroes(
+pub async fn stream_heroes(
     State(state): State<AppState>,
-    Query(params): Query<HeroQueryParams>,
-) -> Result<impl IntoResponse, AppError> {
-    let mut qb = QueryBuilder::<Postgres>::new("SELECT * FROM hero_stats");
-    let mut has_where = false;
+    Query(params): Query<HeroStreamParams>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = state.hero_events.subscribe();
 
-    if let Some(hero_id) = params.hero_id {
-        if !has_where {
-            qb.push(" WHERE ");
-            has_where = true;
-        } else {
-            qb.push(" AND ");
+    let stream = BroadcastStream::new(receiver).filter_map(move |message| match message {
+        Ok(hero) if matches_stream_filters(&hero, &params) => {
+            Some(Ok(Event::default().json_data(&hero).unwrap()))
         }
-        qb.push("hero_id = ").push_bind(hero_id);
+        Ok(_) => None,
+        // A lagging subscriber skips the messages it missed instead of dropping the connection.
+        Err(_) => None,
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Appends the `HeroQueryParams` filters to `qb` as a `WHERE ... AND ...` clause, shared
+/// between the paginated `SELECT` and its companion `COUNT(*)`.
+fn push_hero_filters(qb: &mut QueryBuilder<Postgres>, params: &HeroQueryParams) {
+    let mut has_where = false;
+
+    macro_rules! filter {
+        ($field:expr, $column:literal) => {
+            if let Some(value) = $field.clone() {
+                if !has_where {
+                    qb.push(" WHERE ");
+                    has_where = true;
+                } else {
+                    qb.push(" AND ");
+                }
+                qb.push(concat!($column, " = ")).push_bind(value);
+            }
+        };
     }
 
-    if let Some(region) = params.region {
+    filter!(params.hero_id, "hero_id");
+    filter!(params.region, "region");
+    filter!(params.platform, "platform");
+    filter!(params.gamemode, "gamemode");
+    filter!(params.map, "map");
+    filter!(params.tier, "tier");
+
+    if let Some(start_time) = params.start_time {
         if !has_where {
             qb.push(" WHERE ");
             has_where = true;
         } else {
             qb.push(" AND ");
         }
-        qb.push("region = ").push_bind(region);
+        qb.push("inserted_at >= ").push_bind(start_time);
     }
 
-    if let Some(platform) = params.platform {
+    if let Some(end_time) = params.end_time {
         if !has_where {
             qb.push(" WHERE ");
-            has_where = true;
         } else {
             qb.push(" AND ");
         }
-        qb.push("platform = ").push_bind(platform);
+        qb.push("inserted_at < ").push_bind(end_time);
     }
+}
 
-    if let Some(gamemode) = params.gamemode {
-        if !has_where {
-            qb.push(" WHERE ");
-            has_where = true;
-        } else {
-            qb.push(" AND ");
-        }
-        qb.push("gamemode = ").push_bind(gamemode);
+#[utoipa::path(
+    get,
+    path = "/api/v1/heroes",
+    params(
+        ("hero_id" = Option<String>, Query, description = "Filter by hero id"),
+        ("region" = Option<String>, Query, description = "Filter by region"),
+        ("platform" = Option<String>, Query, description = "Filter by platform"),
+        ("gamemode" = Option<String>, Query, description = "Filter by gamemode"),
+        ("map" = Option<String>, Query, description = "Filter by map"),
+        ("tier" = Option<String>, Query, description = "Filter by tier"),
+        ("start_time" = Option<DateTime<Utc>>, Query, description = "Only rows inserted at or after this time"),
+        ("end_time" = Option<DateTime<Utc>>, Query, description = "Only rows inserted before this time"),
+        ("order_by" = Option<OrderBy>, Query, description = "Column to sort by"),
+        ("order" = Option<Order>, Query, description = "Sort direction, defaults to ascending"),
+        ("limit" = Option<i64>, Query, description = "Max rows to return, capped at 500, defaults to 100"),
+        ("offset" = Option<i64>, Query, description = "Rows to skip, defaults to 0"),
+    ),
+    responses(
+        (status = 200, description = "A page of hero stat rows matching the filters, with pagination metadata", body = HeroesEnvelope),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "heroes"
+)]
+#[instrument(skip(state))]
+pub async fn get_heroes(
+    State(state): State<AppState>,
+    Query(params): Query<HeroQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let mut count_qb = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM hero_stats");
+    push_hero_filters(&mut count_qb, &params);
+    let total: i64 = count_qb
+        .build_query_scalar()
+        .fetch_one(state.db.pool())
+        .await?;
+
+    let mut qb = QueryBuilder::<Postgres>::new("SELECT * FROM hero_stats");
+    push_hero_filters(&mut qb, &params);
+
+    if let Some(order_by) = &params.order_by {
+        let order = params.order.unwrap_or(Order::Asc);
+        qb.push(" ORDER BY ")
+            .push(order_by.to_string())
+            .push(" ")
+            .push(order.to_string());
     }
 
-    if let Some(map) = params.map {
-        if !has_where {
-            qb.push(" WHERE ");
-            has_where = true;
-        } else {
-            qb.push(" AND ");
+    qb.push(" LIMIT ").push_bind(limit);
+    qb.push(" OFFSET ").push_bind(offset);
+
+    let data = qb
+        .build_query_as::<HeroStats>()
+        .fetch_all(state.db.pool())
+        .await?;
+
+    let has_more = offset + data.len() as i64 < total;
+
+    Ok(Json(HeroesEnvelope {
+        data,
+        pagination: Pagination {
+            total,
+            limit,
+            offset,
+            has_more,
+        },
+    }))
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    HeroId,
+    HeroClass,
+    Map,
+    Tier,
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupBy::HeroId => write!(f, "hero_id"),
+            GroupBy::HeroClass => write!(f, "hero_class"),
+            GroupBy::Map => write!(f, "map"),
+            GroupBy::Tier => write!(f, "tier"),
         }
-        qb.push("map = ").push_bind(map);
     }
+}
 
-    if let Some(tier) = params.tier {
-        if !has_where {
-            qb.push(" WHERE ");
-            has_where = true;
-        } else {
-            qb.push(" AND ");
+#[derive(Deserialize, Debug, Clone, Copy, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Bucket {
+    Day,
+    Week,
+}
+
+impl fmt::Display for Bucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bucket::Day => write!(f, "day"),
+            Bucket::Week => write!(f, "week"),
         }
-        qb.push("tier = ").push_bind(tier);
     }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+pub struct HeroAggregateParams {
+    hero_id: Option<String>,
+    region: Option<String>,
+    platform: Option<String>,
+    gamemode: Option<String>,
+    map: Option<String>,
+    tier: Option<String>,
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    group_by: GroupBy,
+    bucket: Bucket,
+}
+
+/// Appends the same filter set as `push_hero_filters`, but sourced from
+/// `HeroAggregateParams` since the aggregate endpoint adds `group_by`/`bucket` on top.
+fn push_aggregate_filters(qb: &mut QueryBuilder<Postgres>, params: &HeroAggregateParams) {
+    let mut has_where = false;
+
+    macro_rules! filter {
+        ($field:expr, $column:literal) => {
+            if let Some(value) = $field.clone() {
+                if !has_where {
+                    qb.push(" WHERE ");
+                    has_where = true;
+                } else {
+                    qb.push(" AND ");
+                }
+                qb.push(concat!($column, " = ")).push_bind(value);
+            }
+        };
+    }
+
+    filter!(params.hero_id, "hero_id");
+    filter!(params.region, "region");
+    filter!(params.platform, "platform");
+    filter!(params.gamemode, "gamemode");
+    filter!(params.map, "map");
+    filter!(params.tier, "tier");
 
     if let Some(start_time) = params.start_time {
         if !has_where {
@@ -162,23 +375,114 @@ pub async fn get_heroes(
         }
         qb.push("inserted_at < ").push_bind(end_time);
     }
+}
 
-    if let Some(order_by) = params.order_by {
-        let order = params.order.unwrap_or(Order::Asc);
-        qb.push(" ORDER BY ")
-            .push(order_by.to_string())
-            .push(" ")
-            .push(order.to_string());
-    }
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct HeroAggregate {
+    group_key: String,
+    bucket: DateTime<Utc>,
+    avg_win_rate: Option<f64>,
+    avg_pick_rate: Option<f64>,
+    min_win_rate: Option<f32>,
+    max_win_rate: Option<f32>,
+    min_pick_rate: Option<f32>,
+    max_pick_rate: Option<f32>,
+    sample_count: i64,
+}
 
-    let heroes = qb
-        .build_query_as::<HeroStats>()
+/// Builds the aggregate query for `params`. `group_by` is cast to `text` because
+/// `hero_class` is a Postgres enum (`hero_class_enum`) and `sqlx`'s `String` decode only
+/// supports text-like column types, not arbitrary enum OIDs.
+fn build_aggregate_query(params: &HeroAggregateParams) -> QueryBuilder<'_, Postgres> {
+    let mut qb = QueryBuilder::<Postgres>::new("SELECT ");
+    qb.push(params.group_by.to_string())
+        .push("::text AS group_key, date_trunc(")
+        .push_bind(params.bucket.to_string())
+        .push(", inserted_at) AS bucket, AVG(win_rate) AS avg_win_rate, AVG(pick_rate) AS avg_pick_rate, MIN(win_rate) AS min_win_rate, MAX(win_rate) AS max_win_rate, MIN(pick_rate) AS min_pick_rate, MAX(pick_rate) AS max_pick_rate, COUNT(*) AS sample_count FROM hero_stats");
+
+    push_aggregate_filters(&mut qb, params);
+
+    qb.push(" GROUP BY group_key, bucket ORDER BY bucket");
+
+    qb
+}
+
+/// Server-side win-rate/pick-rate aggregation so clients can draw tier lists and trend
+/// charts without downloading every raw snapshot row.
+#[utoipa::path(
+    get,
+    path = "/api/v1/heroes/aggregate",
+    params(
+        ("hero_id" = Option<String>, Query, description = "Filter by hero id"),
+        ("region" = Option<String>, Query, description = "Filter by region"),
+        ("platform" = Option<String>, Query, description = "Filter by platform"),
+        ("gamemode" = Option<String>, Query, description = "Filter by gamemode"),
+        ("map" = Option<String>, Query, description = "Filter by map"),
+        ("tier" = Option<String>, Query, description = "Filter by tier"),
+        ("start_time" = Option<DateTime<Utc>>, Query, description = "Only rows inserted at or after this time"),
+        ("end_time" = Option<DateTime<Utc>>, Query, description = "Only rows inserted before this time"),
+        ("group_by" = GroupBy, Query, description = "Column to group aggregates by"),
+        ("bucket" = Bucket, Query, description = "Time bucket interval for trend points"),
+    ),
+    responses(
+        (status = 200, description = "Win-rate/pick-rate aggregates per group and time bucket", body = [HeroAggregate]),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "heroes"
+)]
+#[instrument(skip(state))]
+pub async fn aggregate_heroes(
+    State(state): State<AppState>,
+    Query(params): Query<HeroAggregateParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let aggregates = build_aggregate_query(&params)
+        .build_query_as::<HeroAggregate>()
         .fetch_all(state.db.pool())
         .await?;
 
-    Ok(Json(heroes))
+    Ok(Json(aggregates))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_query_casts_hero_class_to_text() {
+        let params = HeroAggregateParams {
+            hero_id: None,
+            region: None,
+            platform: None,
+            gamemode: None,
+            map: None,
+            tier: None,
+            start_time: None,
+            end_time: None,
+            group_by: GroupBy::HeroClass,
+            bucket: Bucket::Day,
+        };
+
+        let sql = build_aggregate_query(&params).sql();
+
+        assert!(
+            sql.contains("hero_class::text"),
+            "expected the hero_class enum column to be cast to text, got: {sql}"
+        );
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/hero/{id}",
+    params(
+        ("id" = i32, Path, description = "Hero stats row id"),
+    ),
+    responses(
+        (status = 200, description = "The matching hero stats row", body = HeroStats),
+        (status = 404, description = "No hero stats row with that id"),
+    ),
+    tag = "heroes"
+)]
 #[instrument(skip(state))]
 pub async fn get_hero(
     State(state): State<AppState>,
@@ -199,9 +503,21 @@ pub async fn get_hero(
     Ok(JsonResponse(hero))
 }
 
-#[instrument(skip(state))]
+#[utoipa::path(
+    post,
+    path = "/api/v1/heroes",
+    request_body = CreateHeroStats,
+    responses(
+        (status = 201, description = "The created hero stats row", body = HeroStats),
+        (status = 401, description = "Missing or invalid bearer token"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "heroes"
+)]
+#[instrument(skip(state, _user))]
 pub async fn create_hero(
     State(state): State<AppState>,
+    _user: AuthUser,
     Json(hero_data): Json<CreateHeroStats>,
 ) -> Result<(StatusCode, JsonResponse<HeroStats>), AppError> {
     info!("Creating hero: {}", hero_data.hero_id);
@@ -224,12 +540,29 @@ pub async fn create_hero(
     .fetch_one(state.db.pool())
     .await?;
 
+    let _ = state.hero_events.send(hero.clone());
+
     Ok((StatusCode::CREATED, JsonResponse(hero)))
 }
 
-#[instrument(skip(state))]
+#[utoipa::path(
+    delete,
+    path = "/api/v1/hero/{id}",
+    params(
+        ("id" = i32, Path, description = "Hero stats row id"),
+    ),
+    responses(
+        (status = 204, description = "Hero stats row deleted"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "No hero stats row with that id"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "heroes"
+)]
+#[instrument(skip(state, _user))]
 pub async fn delete_hero(
     State(state): State<AppState>,
+    _user: AuthUser,
     Path(id): Path<i32>,
 ) -> Result<StatusCode, AppError> {
     info!("Deleting hero with id: {}", id);
@@ -248,9 +581,22 @@ pub async fn delete_hero(
     Ok(StatusCode::NO_CONTENT)
 }
 
-#[instrument(skip(state))]
+#[utoipa::path(
+    post,
+    path = "/api/v1/heroes/batch",
+    request_body = [CreateHeroStats],
+    responses(
+        (status = 200, description = "Partial-success summary: counts of rows inserted/updated plus any per-row errors", body = Value),
+        (status = 400, description = "Empty batch, or batch larger than the configured max"),
+        (status = 401, description = "Missing or invalid bearer token"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "heroes"
+)]
+#[instrument(skip(state, _user))]
 pub async fn batch_upload(
     State(state): State<AppState>,
+    _user: AuthUser,
     Json(heroes_data): Json<Vec<CreateHeroStats>>,
 ) -> Result<JsonResponse<Value>, AppError> {
     info!("Batch uploading {} heroes", heroes_data.len());
@@ -261,20 +607,32 @@ pub async fn batch_upload(
         });
     }
 
+    if heroes_data.len() > state.config.max_batch_size {
+        return Err(AppError::Validation {
+            message: format!(
+                "Batch of {} exceeds the maximum of {} rows",
+                heroes_data.len(),
+                state.config.max_batch_size
+            ),
+        });
+    }
+
     let mut transaction = state.db.pool().begin().await?;
     let mut created_count = 0;
     let mut errors = Vec::new();
+    let mut committed_heroes = Vec::new();
 
     for (index, hero_data) in heroes_data.iter().enumerate() {
-        let result = sqlx::query(
+        let result = sqlx::query_as::<_, HeroStats>(
             r#"
             INSERT INTO hero_stats (hero_id, pick_rate, win_rate, region, platform, gamemode, map, tier, inserted_at, updated_at)
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW(), NOW())
-            ON CONFLICT (hero_id, region, platform, gamemode, map, tier, inserted_at) 
-            DO UPDATE SET 
+            ON CONFLICT (hero_id, region, platform, gamemode, map, tier, inserted_at)
+            DO UPDATE SET
                 pick_rate = EXCLUDED.pick_rate,
                 win_rate = EXCLUDED.win_rate,
                 updated_at = NOW()
+            RETURNING *
             "#,
         )
         .bind(&hero_data.hero_id)
@@ -285,11 +643,14 @@ pub async fn batch_upload(
         .bind(&hero_data.gamemode)
         .bind(&hero_data.map)
         .bind(&hero_data.tier)
-        .execute(&mut *transaction)
+        .fetch_one(&mut *transaction)
         .await;
 
         match result {
-            Ok(_) => created_count += 1,
+            Ok(hero) => {
+                created_count += 1;
+                committed_heroes.push(hero);
+            }
             Err(e) => {
                 errors.push(json!({
                     "index": index,
@@ -302,6 +663,10 @@ pub async fn batch_upload(
 
     transaction.commit().await?;
 
+    for hero in committed_heroes {
+        let _ = state.hero_events.send(hero);
+    }
+
     Ok(JsonResponse(json!({
         "message": "Batch upload completed",
         "total_submitted": heroes_data.len(),
@@ -0,0 +1,2 @@
+pub mod heroes;
+pub mod status;
@@ -12,13 +12,29 @@ pub fn create_router() -> Router<AppState> {
         .route("/ready", get(ready_check))
 }
 
+#[utoipa::path(
+    get,
+    path = "/ping",
+    responses(
+        (status = 204, description = "Service is responding"),
+    ),
+    tag = "status"
+)]
 #[instrument]
-async fn ping() -> StatusCode {
+pub async fn ping() -> StatusCode {
     StatusCode::NO_CONTENT
 }
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses(
+        (status = 200, description = "Service is healthy", body = Value),
+    ),
+    tag = "status"
+)]
 #[instrument]
-async fn health_check() -> Result<Json<Value>, AppError> {
+pub async fn health_check() -> Result<Json<Value>, AppError> {
     Ok(Json(json!({
         "status": "healthy",
         "service": "ow2stats-backend",
@@ -26,8 +42,16 @@ async fn health_check() -> Result<Json<Value>, AppError> {
     })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/ready",
+    responses(
+        (status = 200, description = "Service is ready to accept traffic", body = Value),
+    ),
+    tag = "status"
+)]
 #[instrument]
-async fn ready_check() -> Result<Json<Value>, AppError> {
+pub async fn ready_check() -> Result<Json<Value>, AppError> {
     Ok(Json(json!({
         "status": "ready",
         "service": "ow2stats-backend",
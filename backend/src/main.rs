@@ -1,29 +1,47 @@
 use axum::{
     body::Body,
+    extract::DefaultBodyLimit,
     http::Request,
-    middleware::{self, Next},
+    middleware::Next,
     response::{Json, Response},
     Router,
 };
 use serde_json::{json, Value};
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::{debug, info, instrument, warn};
 
+mod auth;
 mod config;
 mod database;
 mod error;
 mod handlers;
+mod middleware;
+mod openapi;
 mod routes;
 
 use config::Config;
+use database::models::HeroStats;
 use database::Database;
 use error::AppError;
+use middleware::ratelimit::RateLimiter;
+use openapi::ApiDoc;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+/// Bounds how many unconsumed `HeroStats` events the SSE broadcast channel buffers before
+/// lagging subscribers start skipping messages.
+const HERO_EVENTS_CHANNEL_CAPACITY: usize = 1024;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: Database,
     pub config: Config,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub hero_events: broadcast::Sender<HeroStats>,
 }
 
 #[tokio::main]
@@ -54,9 +72,20 @@ async fn main() -> anyhow::Result<()> {
     db.migrate().await?;
     info!("Database migrations complete");
 
+    let rate_limiter = Arc::new(RateLimiter::new(
+        config.rate_limit_requests_per_window,
+        Duration::from_secs(config.rate_limit_window_secs),
+        &config.trusted_proxies,
+    ));
+    spawn_rate_limiter_sweep(rate_limiter.clone());
+
+    let (hero_events, _) = broadcast::channel(HERO_EVENTS_CHANNEL_CAPACITY);
+
     let state = AppState {
         db,
         config: config.clone(),
+        rate_limiter,
+        hero_events,
     };
 
     let app = create_router(state);
@@ -66,21 +95,42 @@ async fn main() -> anyhow::Result<()> {
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
     info!("Server ready to accept connections");
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
 
+/// Periodically evicts expired rate-limit buckets so memory doesn't grow unbounded with
+/// one-off clients.
+fn spawn_rate_limiter_sweep(rate_limiter: Arc<RateLimiter>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            rate_limiter.sweep();
+        }
+    });
+}
+
 async fn debug_middleware(req: Request<Body>, next: Next) -> Response {
     println!("Request: {} {}", req.method(), req.uri().path());
     next.run(req).await
 }
 
 fn create_router(state: AppState) -> Router {
+    let max_request_body_bytes = state.config.max_request_body_bytes;
+
     handlers::heroes::create_router()
         .merge(handlers::status::create_router())
+        .merge(auth::create_router())
         .with_state(state)
-        .layer(middleware::from_fn(debug_middleware))
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .layer(DefaultBodyLimit::max(max_request_body_bytes))
+        .layer(axum::middleware::from_fn(debug_middleware))
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
 }
@@ -0,0 +1,147 @@
+use std::{
+    collections::{HashMap, HashSet},
+    net::{IpAddr, SocketAddr},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, State},
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use tracing::debug;
+
+use crate::{error::AppError, AppState};
+
+#[derive(Debug)]
+struct Bucket {
+    remaining: u32,
+    window_start: Instant,
+}
+
+/// Fixed-window token bucket limiter keyed by client IP.
+#[derive(Debug)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+    capacity: u32,
+    window: Duration,
+    trusted_proxies: HashSet<IpAddr>,
+}
+
+impl RateLimiter {
+    pub fn new(capacity: u32, window: Duration, trusted_proxies: &str) -> Self {
+        let trusted_proxies = trusted_proxies
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| s.parse().ok())
+            .collect();
+
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            capacity,
+            window,
+            trusted_proxies,
+        }
+    }
+
+    /// Whether `peer` is a configured reverse proxy allowed to set `X-Forwarded-For`.
+    fn trusts_forwarded_for(&self, peer: IpAddr) -> bool {
+        self.trusted_proxies.contains(&peer)
+    }
+
+    /// Refills and decrements the bucket for `ip`, returning the remaining tokens and the
+    /// instant the window resets, or the reset instant alone if the bucket is empty.
+    fn check(&self, ip: IpAddr) -> Result<(u32, Instant), Instant> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let now = Instant::now();
+        let bucket = buckets.entry(ip).or_insert_with(|| Bucket {
+            remaining: self.capacity,
+            window_start: now,
+        });
+
+        if now.duration_since(bucket.window_start) >= self.window {
+            bucket.remaining = self.capacity;
+            bucket.window_start = now;
+        }
+
+        let reset_at = bucket.window_start + self.window;
+
+        if bucket.remaining == 0 {
+            return Err(reset_at);
+        }
+
+        bucket.remaining -= 1;
+        Ok((bucket.remaining, reset_at))
+    }
+
+    /// Drops buckets whose window has elapsed, bounding memory growth from one-off
+    /// clients. Such a bucket would be refilled on its next request anyway, so dropping it
+    /// loses no limiting state.
+    pub fn sweep(&self) {
+        let mut buckets = self.buckets.lock().unwrap();
+        let now = Instant::now();
+        buckets.retain(|_, bucket| now.duration_since(bucket.window_start) < self.window);
+    }
+}
+
+/// Resolves the client IP to key the rate limiter on. The TCP peer from `ConnectInfo` is
+/// authoritative; `X-Forwarded-For` is only honored when that peer is a configured trusted
+/// proxy, so an untrusted client can't pick its own bucket by spoofing the header.
+fn client_ip(req: &Request<Body>, limiter: &RateLimiter) -> IpAddr {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ci| ci.0.ip());
+
+    if let Some(peer) = peer {
+        if limiter.trusts_forwarded_for(peer) {
+            if let Some(forwarded) = req
+                .headers()
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .and_then(|v| v.trim().parse().ok())
+            {
+                return forwarded;
+            }
+        }
+        return peer;
+    }
+
+    IpAddr::from([0, 0, 0, 0])
+}
+
+pub async fn rate_limit(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response, AppError> {
+    let ip = client_ip(&req, &state.rate_limiter);
+
+    match state.rate_limiter.check(ip) {
+        Ok((remaining, reset_at)) => {
+            let mut response = next.run(req).await;
+            let headers = response.headers_mut();
+            headers.insert(
+                "x-ratelimit-remaining",
+                HeaderValue::from_str(&remaining.to_string()).unwrap(),
+            );
+            headers.insert(
+                "x-ratelimit-reset",
+                HeaderValue::from_str(&reset_at.saturating_duration_since(Instant::now()).as_secs().to_string())
+                    .unwrap(),
+            );
+            Ok(response)
+        }
+        Err(reset_at) => {
+            debug!("Rate limit exceeded for {}", ip);
+            Err(AppError::RateLimited {
+                retry_after_secs: reset_at.saturating_duration_since(Instant::now()).as_secs(),
+            })
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use crate::{auth, database::models, handlers};
+
+/// Registers the `bearer_auth` HTTP bearer scheme referenced by `security(...)` on the
+/// JWT-gated handlers, so Swagger UI shows an "Authorize" control for them.
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi
+            .components
+            .as_mut()
+            .expect("ApiDoc registers schemas, so components is always present");
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    modifiers(&SecurityAddon),
+    paths(
+        handlers::heroes::get_heroes,
+        handlers::heroes::get_hero,
+        handlers::heroes::create_hero,
+        handlers::heroes::delete_hero,
+        handlers::heroes::batch_upload,
+        handlers::heroes::aggregate_heroes,
+        handlers::status::ping,
+        handlers::status::health_check,
+        handlers::status::ready_check,
+        auth::issue_token,
+    ),
+    components(schemas(
+        models::HeroStats,
+        models::CreateHeroStats,
+        models::HeroClass,
+        models::MapType,
+        handlers::heroes::HeroQueryParams,
+        handlers::heroes::OrderBy,
+        handlers::heroes::Order,
+        handlers::heroes::Pagination,
+        handlers::heroes::HeroesEnvelope,
+        handlers::heroes::GroupBy,
+        handlers::heroes::Bucket,
+        handlers::heroes::HeroAggregate,
+        auth::TokenRequest,
+        auth::TokenResponse,
+    )),
+    tags(
+        (name = "heroes", description = "Hero stat snapshots scraped from the game"),
+        (name = "status", description = "Service health checks"),
+        (name = "auth", description = "Token issuance for ingestion clients"),
+    )
+)]
+pub struct ApiDoc;